@@ -0,0 +1,82 @@
+//! Benchmarking setup for pallet-did
+
+use super::*;
+use crate::did::Did as DidTrait;
+use crate::Pallet as Did;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+benchmarks! {
+	add_attribute {
+		let v in 0 .. 1024;
+		let caller: T::AccountId = whitelisted_caller();
+		let name = b"benchmark-attribute".to_vec();
+		let value = vec![0u8; v as usize];
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), name, value, None)
+
+	update_attribute {
+		let v in 0 .. 1024;
+		let caller: T::AccountId = whitelisted_caller();
+		let name = b"benchmark-attribute".to_vec();
+		Did::<T>::create_attribute(&caller, &caller, &name, &vec![0u8; 8], None)
+			.map_err(|_| "failed to seed attribute")?;
+		let value = vec![1u8; v as usize];
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), name, value, None)
+
+	read_attribute {
+		let caller: T::AccountId = whitelisted_caller();
+		let name = b"benchmark-attribute".to_vec();
+		Did::<T>::create_attribute(&caller, &caller, &name, &vec![0u8; 8], None)
+			.map_err(|_| "failed to seed attribute")?;
+	}: _(RawOrigin::Signed(caller.clone()), name)
+
+	remove_attribute {
+		let caller: T::AccountId = whitelisted_caller();
+		let name = b"benchmark-attribute".to_vec();
+		Did::<T>::create_attribute(&caller, &caller, &name, &vec![0u8; 8], None)
+			.map_err(|_| "failed to seed attribute")?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), name)
+
+	read_all_attributes {
+		let a in 0 .. T::MaxAttributesPerDid::get();
+		let caller: T::AccountId = whitelisted_caller();
+		for i in 0 .. a {
+			let name = i.to_le_bytes().to_vec();
+			Did::<T>::create_attribute(&caller, &caller, &name, &vec![0u8; 8], None)
+				.map_err(|_| "failed to seed attribute")?;
+		}
+	}: _(RawOrigin::Signed(caller))
+
+	// NOTE: add_attribute_signed/update_attribute_signed are not benchmarked here: producing a
+	// valid T::Signature/T::Public pair requires binding Config to a concrete crypto scheme,
+	// which this generic benchmarking module doesn't do. Their weights in weights.rs are a
+	// conservative hand estimate derived from add_attribute/update_attribute plus the extra
+	// signature-verification work, not a measured benchmark.
+
+	add_delegate {
+		let owner: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let delegate_type = ATTRIBUTE_DELEGATE.to_vec();
+	}: _(RawOrigin::Signed(owner), delegate, delegate_type, None)
+
+	revoke_delegate {
+		let owner: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let delegate_type = ATTRIBUTE_DELEGATE.to_vec();
+		Did::<T>::add_delegate(
+			RawOrigin::Signed(owner.clone()).into(),
+			delegate.clone(),
+			delegate_type.clone(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(owner), delegate, delegate_type)
+
+	transfer_attribute {
+		let owner: T::AccountId = whitelisted_caller();
+		let new_owner: T::AccountId = account("new_owner", 0, 1);
+		let name = b"benchmark-attribute".to_vec();
+		Did::<T>::create_attribute(&owner, &owner, &name, &vec![0u8; 8], None)
+			.map_err(|_| "failed to seed attribute")?;
+	}: _(RawOrigin::Signed(owner), name, new_owner)
+}
@@ -0,0 +1,58 @@
+use crate::structs::Attribute;
+
+/// Errors that can occur while reading or writing a DID's attributes.
+#[derive(Debug, PartialEq)]
+pub enum DidError {
+	/// No attribute was found for the given DID and name.
+	NotFound,
+	/// Attribute name exceeds the maximum allowed length (64 bytes).
+	NameExceedMaxChar,
+	/// An attribute already exists for the given DID and name.
+	AlreadyExist,
+	/// The DID already holds `Config::MaxAttributesPerDid` attributes.
+	TooManyAttributes,
+	/// Attribute creation failed.
+	FailedCreate,
+	/// Attribute update failed.
+	FailedUpdate,
+	/// The acting account is neither the DID owner nor a currently-valid delegate.
+	NotAuthorized,
+}
+
+/// Interface for managing the attributes that make up a DID, implemented by
+/// the pallet. Kept generic over `Signature` so implementations can
+/// authorize writes either directly (a signed extrinsic from the owner) or
+/// via an off-chain signed payload.
+///
+/// `create_attribute`/`mutate_attribute`/`delete_attribute` take both the `actor` performing
+/// the write and the `owner` DID it is performed on, so that an authorized delegate can manage
+/// a DID's attributes without holding its root key.
+pub trait Did<AccountId, BlockNumber, Moment, Signature> {
+	/// Add a new attribute to a DID, with optional expiration.
+	fn create_attribute(
+		actor: &AccountId,
+		owner: &AccountId,
+		name: &[u8],
+		value: &[u8],
+		valid_for: Option<BlockNumber>,
+	) -> Result<(), DidError>;
+
+	/// Update an existing attribute of a DID, with optional expiration.
+	fn mutate_attribute(
+		actor: &AccountId,
+		owner: &AccountId,
+		name: &[u8],
+		value: &[u8],
+		valid_for: Option<BlockNumber>,
+	) -> Result<(), DidError>;
+
+	/// Fetch an attribute of a DID, if it exists.
+	fn get_attribute(owner: &AccountId, name: &[u8]) -> Option<Attribute<BlockNumber, Moment>>;
+
+	/// Remove an existing attribute from a DID.
+	fn delete_attribute(
+		actor: &AccountId,
+		owner: &AccountId,
+		name: &[u8],
+	) -> Result<(), DidError>;
+}
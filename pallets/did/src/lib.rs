@@ -1,10 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 pub mod did;
 pub mod structs;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
 // Re-export did items so that they can be accessed from the crate namespace.
 pub use pallet::*;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -13,9 +22,10 @@ pub mod pallet {
 	use crate::structs::*;
 	use frame_support::pallet_prelude::*;
 	use frame_support::traits::Time as MomentTime;
+	use frame_support::weights::Weight;
 	use frame_system::pallet_prelude::*;
 	use sp_io::hashing::blake2_256;
-	use sp_runtime::traits::{IdentifyAccount, Member, Verify};
+	use sp_runtime::traits::{IdentifyAccount, Member, One, Verify};
 	use sp_std::vec::Vec;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -23,9 +33,21 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type Public: IdentifyAccount<AccountId = Self::AccountId>;
+		/// The public key type that signs off-chain attribute payloads. The caller supplies it
+		/// explicitly alongside the signature; `verify_signature` checks it maps to `identity`
+		/// via `IdentifyAccount` rather than reinterpreting `identity`'s own bytes as a key,
+		/// since the two types aren't generally byte-compatible (e.g. `AccountId32` vs. a
+		/// variant-tagged `MultiSigner`).
+		type Public: IdentifyAccount<AccountId = Self::AccountId> + Member + Decode;
 		type Signature: Verify<Signer = Self::Public> + Member + Decode + Encode;
 		type Time: MomentTime;
+		/// Upper bound on the number of attributes a single DID may hold at once. Caps the cost
+		/// of `read_all_attributes`'s `iter_prefix` scan and the size of the `AttributesRead`
+		/// event, both of which scale with it.
+		#[pallet::constant]
+		type MaxAttributesPerDid: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: crate::weights::WeightInfo;
 	}
 
 	// Pallets use events to inform users when important changes are made.
@@ -38,10 +60,18 @@ pub mod pallet {
 		AttributeAdded(T::AccountId, Vec<u8>, Vec<u8>, Option<T::BlockNumber>),
 		/// Event emitted when an attribute is read successfully
 		AttributeRead(Attribute<T::BlockNumber, <<T as Config>::Time as MomentTime>::Moment>),
+		/// Event emitted when all of a DID's live attributes are read successfully
+		AttributesRead(Vec<Attribute<T::BlockNumber, <<T as Config>::Time as MomentTime>::Moment>>),
 		/// Event emitted when an attribute has been updated. [who, attribute, block]
 		AttributeUpdated(T::AccountId, Vec<u8>, Vec<u8>, Option<T::BlockNumber>),
 		/// Event emitted when an attribute has been deleted. [who, attibute name, block]
 		AttributeRemoved(T::AccountId, Vec<u8>, Option<T::BlockNumber>),
+		/// Event emitted when a delegate has been authorized. [owner, delegate, delegate_type, valid_for]
+		DelegateAdded(T::AccountId, T::AccountId, Vec<u8>, Option<T::BlockNumber>),
+		/// Event emitted when a delegate has been revoked. [owner, delegate, delegate_type]
+		DelegateRevoked(T::AccountId, T::AccountId, Vec<u8>),
+		/// Event emitted when an attribute has been transferred to a new owner. [old_owner, new_owner, attribute name]
+		AttributeTransferred(T::AccountId, T::AccountId, Vec<u8>),
 	}
 
 	#[pallet::error]
@@ -56,6 +86,14 @@ pub mod pallet {
 		AttributeNotFound,
 		// Attribute already exist for a did
 		AttributeAlreadyExist,
+		// DID already holds Config::MaxAttributesPerDid attributes
+		TooManyAttributes,
+		// Off-chain signature did not match the claimed identity and payload
+		InvalidSignature,
+		// Acting account is neither the DID owner nor a currently-valid delegate
+		NotAuthorized,
+		// No matching delegate found for this owner/delegate_type/delegate
+		DelegateNotFound,
 	}
 
 	impl<T: Config> Error<T> {
@@ -66,22 +104,34 @@ pub mod pallet {
 					return Err(Error::<T>::AttributeNameExceedMax64.into())
 				}
 				DidError::AlreadyExist => return Err(Error::<T>::AttributeAlreadyExist.into()),
+				DidError::TooManyAttributes => {
+					return Err(Error::<T>::TooManyAttributes.into())
+				}
 				DidError::FailedCreate => return Err(Error::<T>::AttributeCreationFailed.into()),
 				DidError::FailedUpdate => return Err(Error::<T>::AttributeCreationFailed.into()),
+				DidError::NotAuthorized => return Err(Error::<T>::NotAuthorized.into()),
 			}
 		}
 	}
 
+	/// Delegate type authorizing attribute management (`add_attribute`/`update_attribute`/
+	/// `remove_attribute`) on behalf of a DID.
+	pub const ATTRIBUTE_DELEGATE: &[u8] = b"did-attribute";
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
+	/// Attributes keyed by owner and then by id, so that every attribute belonging to a DID can
+	/// be enumerated with `iter_prefix(owner)` without knowing its name in advance.
 	#[pallet::storage]
 	#[pallet::getter(fn attribute_of)]
-	pub(super) type AttributeStore<T: Config> = StorageMap<
+	pub(super) type AttributeStore<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		(T::AccountId, [u8; 32]),
+		T::AccountId,
+		Blake2_128Concat,
+		[u8; 32],
 		Attribute<T::BlockNumber, <<T as Config>::Time as MomentTime>::Moment>,
 		ValueQuery,
 	>;
@@ -91,8 +141,45 @@ pub mod pallet {
 	pub(super) type AttributeNonce<T: Config> =
 		StorageMap<_, Twox64Concat, (T::AccountId, Vec<u8>), u64, ValueQuery>;
 
+	/// Number of entries `owner` currently holds in `AttributeStore`, kept in lock-step with it
+	/// so `read_all_attributes`/`get_all_attributes` can be charged a weight bounded by
+	/// `Config::MaxAttributesPerDid` without itself having to count the prefix first.
+	#[pallet::storage]
+	#[pallet::getter(fn attribute_count)]
+	pub(super) type AttributeCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Delegates authorized by a DID owner, keyed by `(owner, delegate_type, delegate)` and
+	/// storing the block number at which the delegation expires.
+	#[pallet::storage]
+	#[pallet::getter(fn delegate_of)]
+	pub(super) type DelegateStore<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, Vec<u8>, T::AccountId),
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// Index of attribute ids due to expire at a given block, populated whenever an attribute
+	/// is written with a finite `valid_for`. Lets `on_idle` sweep expired attributes without
+	/// scanning the whole of `AttributeStore`.
+	#[pallet::storage]
+	pub(super) type ExpiryBuckets<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<(T::AccountId, [u8; 32])>, ValueQuery>;
+
+	/// The next expiry bucket `on_idle` hasn't finished sweeping yet. Buckets are swept in
+	/// order so a run that exhausts its weight budget resumes where it left off.
+	#[pallet::storage]
+	#[pallet::getter(fn next_expiry_sweep)]
+	pub(super) type NextExpirySweep<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::sweep_expired(now, remaining_weight)
+		}
+	}
 
 	// Dispatchable functions allow users to interact with the pallet and invoke state changes.
 	// These functions materialize as "extrinsics", which are often compared to transactions.
@@ -101,9 +188,13 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Creates a new attribute as part of a DID
 		/// with optional expiration
-		#[pallet::weight(0)]
+		///
+		/// `did` may be the caller's own account, or another DID for which the caller holds a
+		/// currently-valid `ATTRIBUTE_DELEGATE` delegation.
+		#[pallet::weight(T::WeightInfo::add_attribute(value.len() as u32))]
 		pub fn add_attribute(
 			origin: OriginFor<T>,
+			did: T::AccountId,
 			name: Vec<u8>,
 			value: Vec<u8>,
 			valid_for: Option<T::BlockNumber>,
@@ -116,9 +207,9 @@ pub mod pallet {
 			// Verify that the name len is 64 max
 			ensure!(name.len() <= 64, Error::<T>::AttributeNameExceedMax64);
 
-			match Self::create_attribute(&sender, &name, &value, valid_for) {
+			match Self::create_attribute(&sender, &did, &name, &value, valid_for) {
 				Ok(()) => {
-					Self::deposit_event(Event::AttributeAdded(sender, name, value, valid_for));
+					Self::deposit_event(Event::AttributeAdded(did, name, value, valid_for));
 				}
 				Err(e) => return Error::<T>::dispatch_error(e),
 			};
@@ -128,9 +219,13 @@ pub mod pallet {
 
 		/// Update an existing attribute of a DID
 		/// with optional expiration
-		#[pallet::weight(0)]
+		///
+		/// `did` may be the caller's own account, or another DID for which the caller holds a
+		/// currently-valid `ATTRIBUTE_DELEGATE` delegation.
+		#[pallet::weight(T::WeightInfo::update_attribute(value.len() as u32))]
 		pub fn update_attribute(
 			origin: OriginFor<T>,
+			did: T::AccountId,
 			name: Vec<u8>,
 			value: Vec<u8>,
 			valid_for: Option<T::BlockNumber>,
@@ -143,9 +238,9 @@ pub mod pallet {
 			// Verify that the name len is 64 max
 			ensure!(name.len() <= 64, Error::<T>::AttributeNameExceedMax64);
 
-			match Self::mutate_attribute(&sender, &name, &value, valid_for) {
+			match Self::mutate_attribute(&sender, &did, &name, &value, valid_for) {
 				Ok(()) => {
-					Self::deposit_event(Event::AttributeUpdated(sender, name, value, valid_for));
+					Self::deposit_event(Event::AttributeUpdated(did, name, value, valid_for));
 				}
 				Err(e) => return Error::<T>::dispatch_error(e),
 			};
@@ -153,7 +248,7 @@ pub mod pallet {
 		}
 
 		/// Read did attribute
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::read_attribute())]
 		pub fn read_attribute(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
 			// Check that an extrinsic was signed and get the signer
 			// This fn returns an error if the extrinsic is not signed
@@ -170,9 +265,29 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Read every live (non-expired) attribute belonging to the caller's DID. Lets wallets
+		/// and resolvers fetch a full DID document without already knowing each attribute's name.
+		/// Charged the worst case for `Config::MaxAttributesPerDid`, since the cost of the
+		/// underlying scan and the size of the `AttributesRead` event both scale with it.
+		#[pallet::weight(T::WeightInfo::read_all_attributes(T::MaxAttributesPerDid::get()))]
+		pub fn read_all_attributes(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let attributes = Self::get_all_attributes(&sender);
+			Self::deposit_event(Event::AttributesRead(attributes));
+			Ok(())
+		}
+
 		/// Delete an existing attribute of a DID
-		#[pallet::weight(0)]
-		pub fn remove_attribute(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
+		///
+		/// `did` may be the caller's own account, or another DID for which the caller holds a
+		/// currently-valid `ATTRIBUTE_DELEGATE` delegation.
+		#[pallet::weight(T::WeightInfo::remove_attribute())]
+		pub fn remove_attribute(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			name: Vec<u8>,
+		) -> DispatchResult {
 			// Check that an extrinsic was signed and get the signer
 			// This fn returns an error if the extrinsic is not signed
 			// https://docs.substrate.io/v3/runtime/origins
@@ -181,14 +296,323 @@ pub mod pallet {
 			// Verify that the name len is 64 max
 			ensure!(name.len() <= 64, Error::<T>::AttributeNameExceedMax64);
 
-			match Self::delete_attribute(&sender, &name) {
+			match Self::delete_attribute(&sender, &did, &name) {
 				Ok(()) => {
 					// Get the block number from the FRAME system pallet
 					let current_block = Some(<frame_system::Pallet<T>>::block_number());
-					Self::deposit_event(Event::AttributeRemoved(sender, name, current_block));
+					Self::deposit_event(Event::AttributeRemoved(did, name, current_block));
+				}
+				Err(e) => return Error::<T>::dispatch_error(e),
+			};
+			Ok(())
+		}
+
+		/// Transfer a single attribute from the caller to `new_owner`, re-keying it under
+		/// `new_owner`'s current nonce. Fails if the caller holds no such attribute, or if
+		/// `new_owner` already holds a live attribute of that name.
+		#[pallet::weight(T::WeightInfo::transfer_attribute())]
+		pub fn transfer_attribute(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			// Verify that the name len is 64 max
+			ensure!(name.len() <= 64, Error::<T>::AttributeNameExceedMax64);
+
+			match Self::transfer_attribute_to(&sender, &new_owner, &name) {
+				Ok(()) => {
+					Self::deposit_event(Event::AttributeTransferred(sender, new_owner, name));
+				}
+				Err(e) => return Error::<T>::dispatch_error(e),
+			};
+
+			Ok(())
+		}
+
+		/// Authorize `delegate` as a `delegate_type` delegate of the caller's DID, until
+		/// `valid_for` blocks from now (or indefinitely if `None`). Only an
+		/// `ATTRIBUTE_DELEGATE` delegation lets the delegate manage the DID's attributes.
+		#[pallet::weight(T::WeightInfo::add_delegate())]
+		pub fn add_delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			delegate_type: Vec<u8>,
+			valid_for: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(delegate_type.len() <= 64, Error::<T>::AttributeNameExceedMax64);
+
+			let now_block_number = <frame_system::Pallet<T>>::block_number();
+			let validity: T::BlockNumber = match valid_for {
+				Some(blocks) => now_block_number + blocks,
+				None => u32::max_value().into(),
+			};
+
+			<DelegateStore<T>>::insert((&owner, delegate_type.clone(), &delegate), validity);
+			Self::deposit_event(Event::DelegateAdded(owner, delegate, delegate_type, valid_for));
+
+			Ok(())
+		}
+
+		/// Revoke a delegate previously authorized with `add_delegate`.
+		#[pallet::weight(T::WeightInfo::revoke_delegate())]
+		pub fn revoke_delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			delegate_type: Vec<u8>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(
+				<DelegateStore<T>>::contains_key((&owner, delegate_type.clone(), &delegate)),
+				Error::<T>::DelegateNotFound
+			);
+
+			<DelegateStore<T>>::remove((&owner, delegate_type.clone(), &delegate));
+			Self::deposit_event(Event::DelegateRevoked(owner, delegate, delegate_type));
+
+			Ok(())
+		}
+
+		/// Add a new attribute on behalf of `identity`, authorized by an off-chain signature
+		/// rather than a direct extrinsic from `identity` itself. Lets a relayer pay the
+		/// transaction fee while the DID owner authorizes the write by signing the payload.
+		/// `public` must be the public key identifying `identity`.
+		#[pallet::weight(T::WeightInfo::add_attribute_signed(value.len() as u32))]
+		pub fn add_attribute_signed(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			public: T::Public,
+			name: Vec<u8>,
+			value: Vec<u8>,
+			valid_for: Option<T::BlockNumber>,
+			signature: T::Signature,
+		) -> DispatchResult {
+			// Anyone may relay a signed write; only the signature over the payload
+			// authorizes the change, not the extrinsic's own signer.
+			let _relayer = ensure_signed(origin)?;
+
+			// Verify that the name len is 64 max
+			ensure!(name.len() <= 64, Error::<T>::AttributeNameExceedMax64);
+
+			Self::verify_signature(&identity, &public, &name, &value, valid_for, &signature)?;
+
+			match Self::create_attribute(&identity, &identity, &name, &value, valid_for) {
+				Ok(()) => {
+					Self::deposit_event(Event::AttributeAdded(identity, name, value, valid_for));
+				}
+				Err(e) => return Error::<T>::dispatch_error(e),
+			};
+
+			Ok(())
+		}
+
+		/// Update an existing attribute on behalf of `identity`, authorized by an off-chain
+		/// signature. See [`Pallet::add_attribute_signed`].
+		#[pallet::weight(T::WeightInfo::update_attribute_signed(value.len() as u32))]
+		pub fn update_attribute_signed(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			public: T::Public,
+			name: Vec<u8>,
+			value: Vec<u8>,
+			valid_for: Option<T::BlockNumber>,
+			signature: T::Signature,
+		) -> DispatchResult {
+			let _relayer = ensure_signed(origin)?;
+
+			// Verify that the name len is 64 max
+			ensure!(name.len() <= 64, Error::<T>::AttributeNameExceedMax64);
+
+			Self::verify_signature(&identity, &public, &name, &value, valid_for, &signature)?;
+
+			match Self::mutate_attribute(&identity, &identity, &name, &value, valid_for) {
+				Ok(()) => {
+					Self::deposit_event(Event::AttributeUpdated(identity, name, value, valid_for));
 				}
 				Err(e) => return Error::<T>::dispatch_error(e),
 			};
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Verify that `signature`, made with `public`, authorizes `identity` to write the given
+		/// attribute payload. `public` must map to `identity` via `IdentifyAccount` — it is not
+		/// derived from `identity`'s bytes, since `Public`'s encoding generally isn't
+		/// byte-compatible with `AccountId`'s. The current value of `AttributeNonce` for
+		/// `(identity, name)` is folded into the signed payload so that once a signature is
+		/// consumed by `create_attribute` or `mutate_attribute` (which advance the nonce), it
+		/// cannot be replayed.
+		fn verify_signature(
+			identity: &T::AccountId,
+			public: &T::Public,
+			name: &Vec<u8>,
+			value: &Vec<u8>,
+			valid_for: Option<T::BlockNumber>,
+			signature: &T::Signature,
+		) -> DispatchResult {
+			ensure!(public.clone().into_account() == *identity, Error::<T>::InvalidSignature);
+
+			let nonce = Self::nonce_of((identity, name.clone()));
+			let payload = (identity, name, value, valid_for, nonce).using_encoded(blake2_256);
+
+			ensure!(signature.verify(&payload[..], public), Error::<T>::InvalidSignature);
+
+			Ok(())
+		}
+
+		/// Returns true if `delegate` currently holds a non-expired `delegate_type` delegation
+		/// from `owner`.
+		pub fn valid_delegate(
+			owner: &T::AccountId,
+			delegate_type: &Vec<u8>,
+			delegate: &T::AccountId,
+		) -> bool {
+			match Self::delegate_of((owner, delegate_type.clone(), delegate)) {
+				Some(expiry) => expiry > <frame_system::Pallet<T>>::block_number(),
+				None => false,
+			}
+		}
+
+		/// Returns `Ok(())` if `actor` may manage `owner`'s attributes: either `actor` is
+		/// `owner` itself, or `actor` holds a currently-valid `ATTRIBUTE_DELEGATE` delegation
+		/// from `owner`.
+		fn ensure_authorized(actor: &T::AccountId, owner: &T::AccountId) -> Result<(), DidError> {
+			if actor == owner || Self::valid_delegate(owner, &ATTRIBUTE_DELEGATE.to_vec(), actor) {
+				return Ok(());
+			}
+			Err(DidError::NotAuthorized)
+		}
+
+		/// All of `owner`'s live (non-expired) attributes, keyed by the `AttributeStore` owner
+		/// prefix. Lets a client resolve a full DID document without already knowing each
+		/// attribute's name.
+		pub fn get_all_attributes(
+			owner: &T::AccountId,
+		) -> Vec<Attribute<T::BlockNumber, <<T as Config>::Time as MomentTime>::Moment>> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			<AttributeStore<T>>::iter_prefix(owner)
+				.filter_map(|(_, attr)| if attr.validity > now { Some(attr) } else { None })
+				.collect()
+		}
+
+		/// Index `id` under `validity`'s expiry bucket so `on_idle` can sweep it once it's
+		/// passed. Attributes with no expiration (`validity == u32::max_value()`, which is what
+		/// a `None` `valid_for` is stored as) are never indexed since they're never swept.
+		fn schedule_expiry(owner: &T::AccountId, id: [u8; 32], validity: T::BlockNumber) {
+			if validity == u32::max_value().into() {
+				return;
+			}
+			<ExpiryBuckets<T>>::mutate(validity, |entries| entries.push((owner.clone(), id)));
+		}
+
+		/// Undo a previous `schedule_expiry`, e.g. because the attribute was re-keyed or
+		/// removed before it expired.
+		fn cancel_expiry(owner: &T::AccountId, id: [u8; 32], validity: T::BlockNumber) {
+			if validity == u32::max_value().into() {
+				return;
+			}
+			<ExpiryBuckets<T>>::mutate(validity, |entries| {
+				entries.retain(|entry| entry != &(owner.clone(), id))
+			});
+		}
+
+		/// Remove attributes whose expiry bucket is `<= now`, bounded by `remaining_weight`.
+		/// Buckets are swept in order starting from `NextExpirySweep`; a bucket that doesn't
+		/// fully fit in the budget is left in storage (minus whatever was already removed) and
+		/// picked up again on the next call.
+		fn sweep_expired(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			// A bucket is read once and then removed or rewritten; an entry reads and writes
+			// both `AttributeStore` and `AttributeCount`.
+			let read_bucket = T::DbWeight::get().reads_writes(1, 1);
+			let per_entry = T::DbWeight::get().reads_writes(2, 2);
+			// Reserve the read and final write of `NextExpirySweep` up front.
+			let cursor_access = T::DbWeight::get().reads_writes(1, 1);
+			if cursor_access > remaining_weight {
+				return 0;
+			}
+			let mut consumed: Weight = cursor_access;
+
+			let mut cursor = Self::next_expiry_sweep();
+			while cursor <= now {
+				if consumed.saturating_add(read_bucket) > remaining_weight {
+					break;
+				}
+				consumed = consumed.saturating_add(read_bucket);
+
+				let entries = <ExpiryBuckets<T>>::get(cursor);
+				let mut leftover = Vec::new();
+				for (owner, id) in entries {
+					if consumed.saturating_add(per_entry) > remaining_weight {
+						leftover.push((owner, id));
+						continue;
+					}
+					consumed = consumed.saturating_add(per_entry);
+
+					if <AttributeStore<T>>::contains_key(&owner, &id) {
+						let attr = Self::attribute_of(&owner, &id);
+						<AttributeStore<T>>::remove(&owner, &id);
+						<AttributeCount<T>>::mutate(&owner, |count| *count = count.saturating_sub(1));
+						Self::deposit_event(Event::AttributeRemoved(owner, attr.name, Some(now)));
+					}
+				}
+
+				if leftover.is_empty() {
+					<ExpiryBuckets<T>>::remove(cursor);
+					cursor = cursor.saturating_add(One::one());
+				} else {
+					<ExpiryBuckets<T>>::insert(cursor, leftover);
+					break;
+				}
+			}
+
+			NextExpirySweep::<T>::put(cursor);
+			consumed
+		}
+
+		/// Move a single attribute from `owner` to `new_owner`, recomputing its storage id
+		/// against `new_owner`'s current nonce and carrying over `value`/`created`/`validity`.
+		fn transfer_attribute_to(
+			owner: &T::AccountId,
+			new_owner: &T::AccountId,
+			name: &[u8],
+		) -> Result<(), DidError> {
+			let attr = Self::get_attribute(owner, name).ok_or(DidError::NotFound)?;
+			if Self::get_attribute(new_owner, name).is_some() {
+				return Err(DidError::AlreadyExist);
+			}
+
+			ensure!(
+				Self::attribute_count(new_owner) < T::MaxAttributesPerDid::get(),
+				DidError::TooManyAttributes
+			);
+
+			let nonce = Self::nonce_of((owner, name.to_vec()));
+			let old_id = (owner, name, nonce.saturating_sub(1)).using_encoded(blake2_256);
+			<AttributeStore<T>>::remove(owner, &old_id);
+			<AttributeCount<T>>::mutate(owner, |count| *count = count.saturating_sub(1));
+			Self::cancel_expiry(owner, old_id, attr.validity);
+
+			let new_nonce = Self::nonce_of((new_owner, name.to_vec()));
+			let new_id = (new_owner, name, new_nonce).using_encoded(blake2_256);
+			let transferred = Attribute {
+				name: name.to_vec(),
+				value: attr.value,
+				validity: attr.validity,
+				created: attr.created,
+				nonce: new_nonce,
+			};
+
+			<AttributeStore<T>>::insert(new_owner, &new_id, transferred);
+			<AttributeNonce<T>>::insert((new_owner, name.to_vec()), new_nonce.saturating_add(1));
+			<AttributeCount<T>>::mutate(new_owner, |count| *count = count.saturating_add(1));
+			Self::schedule_expiry(new_owner, new_id, attr.validity);
+
 			Ok(())
 		}
 	}
@@ -200,11 +624,20 @@ pub mod pallet {
 	{
 		// Add new attribute to a did
 		fn create_attribute(
+			actor: &T::AccountId,
 			owner: &T::AccountId,
 			name: &[u8],
 			value: &[u8],
 			valid_for: Option<T::BlockNumber>,
 		) -> Result<(), DidError> {
+			Self::ensure_authorized(actor, owner)?;
+
+			ensure!(Self::get_attribute(owner, name).is_none(), DidError::AlreadyExist);
+			ensure!(
+				Self::attribute_count(owner) < T::MaxAttributesPerDid::get(),
+				DidError::TooManyAttributes
+			);
+
 			let now_timestamp = T::Time::now();
 			let now_block_number = <frame_system::Pallet<T>>::block_number();
 			let validity: T::BlockNumber = match valid_for {
@@ -212,7 +645,9 @@ pub mod pallet {
 				None => u32::max_value().into(),
 			};
 
-			// Generate nonce for integrity check
+			// The current nonce both derives this attribute's storage id and is folded
+			// into signed payloads for replay protection, so it must advance on every
+			// write; bump it here so the same id isn't handed out a second time.
 			let nonce = Self::nonce_of((&owner, name.to_vec()));
 			let id = (&owner, name, nonce).using_encoded(blake2_256);
 			let new_attribute = Attribute {
@@ -223,18 +658,24 @@ pub mod pallet {
 				nonce,
 			};
 
-			<AttributeStore<T>>::insert((&owner, &id), new_attribute);
+			<AttributeStore<T>>::insert(&owner, &id, new_attribute);
+			<AttributeNonce<T>>::insert((&owner, name.to_vec()), nonce.saturating_add(1));
+			<AttributeCount<T>>::mutate(owner, |count| *count = count.saturating_add(1));
+			Self::schedule_expiry(owner, id, validity);
 
 			Ok(())
 		}
 
 		// Update existing attribute on a did
 		fn mutate_attribute(
+			actor: &T::AccountId,
 			owner: &T::AccountId,
 			name: &[u8],
 			value: &[u8],
 			valid_for: Option<T::BlockNumber>,
 		) -> Result<(), DidError> {
+			Self::ensure_authorized(actor, owner)?;
+
 			let now_block_number = <frame_system::Pallet<T>>::block_number();
 			let validity: T::BlockNumber = match valid_for {
 				Some(blocks) => now_block_number + blocks,
@@ -246,12 +687,23 @@ pub mod pallet {
 
 			match attribute {
 				Some(mut attr) => {
+					// The currently stored attribute was created/last mutated with
+					// `nonce - 1` (see `get_attribute`); re-key it under a fresh id at
+					// the current nonce so a consumed signature can't be replayed to
+					// silently revert this update.
 					let nonce = Self::nonce_of((&owner, name.to_vec()));
-					let id = (&owner, name, nonce).using_encoded(blake2_256);
+					let old_id = (&owner, name, nonce.saturating_sub(1)).using_encoded(blake2_256);
+					let new_id = (&owner, name, nonce).using_encoded(blake2_256);
+					let old_validity = attr.validity;
 					attr.value = (&value).to_vec();
 					attr.validity = validity;
+					attr.nonce = nonce;
 
-					<AttributeStore<T>>::mutate((&owner, &id), |a| *a = attr);
+					<AttributeStore<T>>::remove(&owner, &old_id);
+					<AttributeStore<T>>::insert(&owner, &new_id, attr);
+					<AttributeNonce<T>>::insert((&owner, name.to_vec()), nonce.saturating_add(1));
+					Self::cancel_expiry(owner, old_id, old_validity);
+					Self::schedule_expiry(owner, new_id, validity);
 					Ok(())
 				}
 				None => Err(DidError::NotFound),
@@ -263,26 +715,43 @@ pub mod pallet {
 			owner: &T::AccountId,
 			name: &[u8],
 		) -> Option<Attribute<T::BlockNumber, <<T as Config>::Time as MomentTime>::Moment>> {
-			// Generate nounce for integrity check
+			// The stored attribute was written against `nonce - 1`: `AttributeNonce`
+			// holds the nonce to hand out to the *next* write, not the current one.
 			let nonce = Self::nonce_of((&owner, name.to_vec()));
-			let id = (&owner, name, nonce).using_encoded(blake2_256);
+			let id = (&owner, name, nonce.saturating_sub(1)).using_encoded(blake2_256);
 
-			if <AttributeStore<T>>::contains_key((&owner, &id)) {
-				return Some(Self::attribute_of((&owner, &id)));
+			if !<AttributeStore<T>>::contains_key(&owner, &id) {
+				return None;
 			}
-			None
+
+			let attr = Self::attribute_of(&owner, &id);
+			// An expired attribute reads as if it were never there; `on_idle` will
+			// eventually sweep it out of `AttributeStore` for good.
+			if attr.validity <= <frame_system::Pallet<T>>::block_number() {
+				return None;
+			}
+			Some(attr)
 		}
 
 		// Delete an attribute from a did
-		fn delete_attribute(owner: &T::AccountId, name: &[u8]) -> Result<(), DidError> {
-			// Generate nounce for integrity check
+		fn delete_attribute(
+			actor: &T::AccountId,
+			owner: &T::AccountId,
+			name: &[u8],
+		) -> Result<(), DidError> {
+			Self::ensure_authorized(actor, owner)?;
+
+			// See `get_attribute` for why `nonce - 1` identifies the live attribute.
 			let nonce = Self::nonce_of((&owner, name.to_vec()));
-			let id = (&owner, name, nonce).using_encoded(blake2_256);
+			let id = (&owner, name, nonce.saturating_sub(1)).using_encoded(blake2_256);
 
-			if !<AttributeStore<T>>::contains_key((&owner, &id)) {
+			if !<AttributeStore<T>>::contains_key(&owner, &id) {
 				return Err(DidError::NotFound);
 			}
-			<AttributeStore<T>>::remove((&owner, &id));
+			let attr = Self::attribute_of(&owner, &id);
+			<AttributeStore<T>>::remove(&owner, &id);
+			<AttributeCount<T>>::mutate(owner, |count| *count = count.saturating_sub(1));
+			Self::cancel_expiry(owner, id, attr.validity);
 			Ok(())
 		}
 	}
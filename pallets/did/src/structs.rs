@@ -0,0 +1,17 @@
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// An attribute attached to a DID, addressable by `name` under the owning
+/// account. `validity` is the first block number at which the attribute is no
+/// longer considered live, and `nonce` is the value of `AttributeNonce` that
+/// was consumed to derive this attribute's storage id.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+pub struct Attribute<BlockNumber, Moment> {
+	pub name: Vec<u8>,
+	pub value: Vec<u8>,
+	pub validity: BlockNumber,
+	pub created: Moment,
+	pub nonce: u64,
+}
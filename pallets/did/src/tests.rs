@@ -0,0 +1,367 @@
+use crate::{
+	mock::{new_test_ext, Did, Origin, System, Test},
+	Error,
+};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use sp_core::{sr25519, Pair};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{traits::IdentifyAccount, MultiSignature, MultiSigner};
+
+fn account(pair: &sr25519::Pair) -> sp_runtime::AccountId32 {
+	MultiSigner::from(pair.public()).into_account()
+}
+
+#[test]
+fn create_attribute_rejects_duplicate_name() {
+	new_test_ext().execute_with(|| {
+		let who = sp_runtime::AccountId32::new([1u8; 32]);
+		assert_ok!(Did::add_attribute(
+			Origin::signed(who.clone()),
+			who.clone(),
+			b"name".to_vec(),
+			b"value".to_vec(),
+			None
+		));
+
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(who.clone()),
+				who,
+				b"name".to_vec(),
+				b"other-value".to_vec(),
+				None
+			),
+			Error::<Test>::AttributeAlreadyExist
+		);
+	});
+}
+
+#[test]
+fn update_attribute_advances_nonce_without_losing_the_value() {
+	new_test_ext().execute_with(|| {
+		let who = sp_runtime::AccountId32::new([1u8; 32]);
+		assert_ok!(Did::add_attribute(
+			Origin::signed(who.clone()),
+			who.clone(),
+			b"name".to_vec(),
+			b"v1".to_vec(),
+			None
+		));
+		assert_ok!(Did::update_attribute(
+			Origin::signed(who.clone()),
+			who.clone(),
+			b"name".to_vec(),
+			b"v2".to_vec(),
+			None
+		));
+
+		let attr =
+			<crate::Pallet<Test> as crate::did::Did<_, _, _, _>>::get_attribute(&who, b"name")
+				.expect("attribute still readable after update");
+		assert_eq!(attr.value, b"v2".to_vec());
+		// add consumed nonce 0; the update re-keys the attribute under nonce 1.
+		assert_eq!(attr.nonce, 1);
+	});
+}
+
+#[test]
+fn delete_then_recreate_is_allowed() {
+	new_test_ext().execute_with(|| {
+		let who = sp_runtime::AccountId32::new([1u8; 32]);
+		assert_ok!(Did::add_attribute(
+			Origin::signed(who.clone()),
+			who.clone(),
+			b"name".to_vec(),
+			b"v1".to_vec(),
+			None
+		));
+		assert_ok!(Did::remove_attribute(
+			Origin::signed(who.clone()),
+			who.clone(),
+			b"name".to_vec()
+		));
+		assert_ok!(Did::add_attribute(
+			Origin::signed(who.clone()),
+			who,
+			b"name".to_vec(),
+			b"v2".to_vec(),
+			None
+		));
+	});
+}
+
+#[test]
+fn transfer_moves_the_attribute_to_the_new_owner() {
+	new_test_ext().execute_with(|| {
+		let owner = sp_runtime::AccountId32::new([1u8; 32]);
+		let new_owner = sp_runtime::AccountId32::new([2u8; 32]);
+		assert_ok!(Did::add_attribute(
+			Origin::signed(owner.clone()),
+			owner.clone(),
+			b"name".to_vec(),
+			b"value".to_vec(),
+			None
+		));
+
+		assert_ok!(Did::transfer_attribute(
+			Origin::signed(owner.clone()),
+			b"name".to_vec(),
+			new_owner.clone()
+		));
+
+		assert!(
+			<crate::Pallet<Test> as crate::did::Did<_, _, _, _>>::get_attribute(&owner, b"name")
+				.is_none()
+		);
+		assert!(<crate::Pallet<Test> as crate::did::Did<_, _, _, _>>::get_attribute(
+			&new_owner,
+			b"name"
+		)
+		.is_some());
+	});
+}
+
+#[test]
+fn expired_attribute_is_swept_by_on_idle() {
+	new_test_ext().execute_with(|| {
+		let who = sp_runtime::AccountId32::new([1u8; 32]);
+		assert_ok!(Did::add_attribute(
+			Origin::signed(who.clone()),
+			who.clone(),
+			b"name".to_vec(),
+			b"value".to_vec(),
+			Some(1)
+		));
+
+		System::set_block_number(5);
+		assert!(
+			<crate::Pallet<Test> as crate::did::Did<_, _, _, _>>::get_attribute(&who, b"name")
+				.is_none(),
+			"expired attribute must read as absent even before it's swept"
+		);
+
+		<crate::Pallet<Test> as Hooks<u64>>::on_idle(5, Weight::MAX);
+		assert!(!crate::AttributeStore::<Test>::iter_prefix(&who)
+			.any(|(_, attr)| attr.name == b"name".to_vec()));
+	});
+}
+
+#[test]
+fn create_attribute_is_capped_at_max_attributes_per_did() {
+	new_test_ext().execute_with(|| {
+		let who = sp_runtime::AccountId32::new([1u8; 32]);
+		for i in 0..16u32 {
+			assert_ok!(Did::add_attribute(
+				Origin::signed(who.clone()),
+				who.clone(),
+				i.to_le_bytes().to_vec(),
+				b"value".to_vec(),
+				None
+			));
+		}
+
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(who.clone()),
+				who,
+				b"one-too-many".to_vec(),
+				b"value".to_vec(),
+				None
+			),
+			Error::<Test>::TooManyAttributes
+		);
+	});
+}
+
+#[test]
+fn delegate_can_manage_the_owners_attribute() {
+	new_test_ext().execute_with(|| {
+		let owner = sp_runtime::AccountId32::new([1u8; 32]);
+		let delegate = sp_runtime::AccountId32::new([2u8; 32]);
+		assert_ok!(Did::add_delegate(
+			Origin::signed(owner.clone()),
+			delegate.clone(),
+			crate::ATTRIBUTE_DELEGATE.to_vec(),
+			None
+		));
+
+		assert_ok!(Did::add_attribute(
+			Origin::signed(delegate.clone()),
+			owner.clone(),
+			b"name".to_vec(),
+			b"v1".to_vec(),
+			None
+		));
+		assert_ok!(Did::update_attribute(
+			Origin::signed(delegate.clone()),
+			owner.clone(),
+			b"name".to_vec(),
+			b"v2".to_vec(),
+			None
+		));
+		assert_ok!(Did::remove_attribute(
+			Origin::signed(delegate),
+			owner,
+			b"name".to_vec()
+		));
+	});
+}
+
+#[test]
+fn non_owner_non_delegate_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let owner = sp_runtime::AccountId32::new([1u8; 32]);
+		let stranger = sp_runtime::AccountId32::new([3u8; 32]);
+
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(stranger),
+				owner,
+				b"name".to_vec(),
+				b"value".to_vec(),
+				None
+			),
+			Error::<Test>::NotAuthorized
+		);
+	});
+}
+
+#[test]
+fn expired_delegate_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let owner = sp_runtime::AccountId32::new([1u8; 32]);
+		let delegate = sp_runtime::AccountId32::new([2u8; 32]);
+		assert_ok!(Did::add_delegate(
+			Origin::signed(owner.clone()),
+			delegate.clone(),
+			crate::ATTRIBUTE_DELEGATE.to_vec(),
+			Some(5)
+		));
+
+		System::set_block_number(6);
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(delegate),
+				owner,
+				b"name".to_vec(),
+				b"value".to_vec(),
+				None
+			),
+			Error::<Test>::NotAuthorized
+		);
+	});
+}
+
+#[test]
+fn revoked_delegate_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let owner = sp_runtime::AccountId32::new([1u8; 32]);
+		let delegate = sp_runtime::AccountId32::new([2u8; 32]);
+		assert_ok!(Did::add_delegate(
+			Origin::signed(owner.clone()),
+			delegate.clone(),
+			crate::ATTRIBUTE_DELEGATE.to_vec(),
+			None
+		));
+		assert_ok!(Did::revoke_delegate(
+			Origin::signed(owner.clone()),
+			delegate.clone(),
+			crate::ATTRIBUTE_DELEGATE.to_vec()
+		));
+
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(delegate),
+				owner,
+				b"name".to_vec(),
+				b"value".to_vec(),
+				None
+			),
+			Error::<Test>::NotAuthorized
+		);
+	});
+}
+
+#[test]
+fn signed_write_with_the_real_account_succeeds() {
+	new_test_ext().execute_with(|| {
+		let pair = sr25519::Pair::from_seed(&[7u8; 32]);
+		let identity = account(&pair);
+		let name = b"name".to_vec();
+		let value = b"value".to_vec();
+
+		let payload =
+			(&identity, &name, &value, Option::<u64>::None, 0u64).using_encoded(blake2_256);
+		let signature: MultiSignature = pair.sign(&payload[..]).into();
+
+		assert_ok!(Did::add_attribute_signed(
+			Origin::signed(identity.clone()),
+			identity.clone(),
+			MultiSigner::from(pair.public()),
+			name.clone(),
+			value.clone(),
+			None,
+			signature
+		));
+
+		let attr =
+			<crate::Pallet<Test> as crate::did::Did<_, _, _, _>>::get_attribute(&identity, &name)
+				.expect("signed write landed");
+		assert_eq!(attr.value, value);
+	});
+}
+
+#[test]
+fn signed_write_rejects_a_public_key_for_a_different_identity() {
+	new_test_ext().execute_with(|| {
+		let signer = sr25519::Pair::from_seed(&[7u8; 32]);
+		let claimed_identity = sp_runtime::AccountId32::new([9u8; 32]);
+		let name = b"name".to_vec();
+		let value = b"value".to_vec();
+
+		let payload = (&claimed_identity, &name, &value, Option::<u64>::None, 0u64)
+			.using_encoded(blake2_256);
+		let signature: MultiSignature = signer.sign(&payload[..]).into();
+
+		assert_noop!(
+			Did::add_attribute_signed(
+				Origin::signed(claimed_identity.clone()),
+				claimed_identity,
+				MultiSigner::from(signer.public()),
+				name,
+				value,
+				None,
+				signature
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn signed_write_rejects_a_tampered_payload() {
+	new_test_ext().execute_with(|| {
+		let pair = sr25519::Pair::from_seed(&[7u8; 32]);
+		let identity = account(&pair);
+		let name = b"name".to_vec();
+		let value = b"value".to_vec();
+
+		let payload =
+			(&identity, &name, &value, Option::<u64>::None, 0u64).using_encoded(blake2_256);
+		let signature: MultiSignature = pair.sign(&payload[..]).into();
+
+		assert_noop!(
+			Did::add_attribute_signed(
+				Origin::signed(identity.clone()),
+				identity,
+				MultiSigner::from(pair.public()),
+				name,
+				b"tampered-value".to_vec(),
+				None,
+				signature
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
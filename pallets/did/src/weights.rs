@@ -0,0 +1,176 @@
+//! Weights for pallet_did
+//!
+//! These are hand-estimated placeholders, NOT output from the Substrate benchmark CLI: this
+//! tree has no buildable `peaq-node` binary to run `benchmark pallet` against, and
+//! `benchmarking.rs` is wired up to `frame_benchmarking::benchmarks!` for when one exists.
+//! Replace every number in this file with real output from:
+//!
+//!   ./target/release/peaq-node benchmark pallet \
+//!       --chain=dev --pallet=pallet_did --extrinsic=* \
+//!       --steps=50 --repeat=20 --output=./pallets/did/src/weights.rs
+//!
+//! before relying on these for fee calculation or spam resistance in a live runtime.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_did.
+pub trait WeightInfo {
+	fn add_attribute(v: u32) -> Weight;
+	fn update_attribute(v: u32) -> Weight;
+	fn read_attribute() -> Weight;
+	fn remove_attribute() -> Weight;
+	fn add_attribute_signed(v: u32) -> Weight;
+	fn update_attribute_signed(v: u32) -> Weight;
+	fn add_delegate() -> Weight;
+	fn revoke_delegate() -> Weight;
+	fn transfer_attribute() -> Weight;
+	fn read_all_attributes(a: u32) -> Weight;
+}
+
+/// Weights for pallet_did using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Did AttributeNonce (r:1 w:1)
+	// Storage: Did AttributeStore (r:1 w:1)
+	// Storage: Did AttributeCount (r:1 w:1)
+	// Storage: Did ExpiryBuckets (r:1 w:1)
+	fn add_attribute(v: u32) -> Weight {
+		(45_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Did AttributeNonce (r:1 w:1)
+	// Storage: Did AttributeStore (r:1 w:2)
+	// Storage: Did ExpiryBuckets (r:2 w:2)
+	fn update_attribute(v: u32) -> Weight {
+		(48_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_100 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Did AttributeNonce (r:1 w:0)
+	// Storage: Did AttributeStore (r:1 w:0)
+	fn read_attribute() -> Weight {
+		(22_000_000 as Weight).saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+	// Storage: Did AttributeNonce (r:1 w:0)
+	// Storage: Did AttributeStore (r:1 w:1)
+	// Storage: Did AttributeCount (r:1 w:1)
+	// Storage: Did ExpiryBuckets (r:1 w:1)
+	fn remove_attribute() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Did AttributeNonce (r:1 w:1)
+	// Storage: Did AttributeStore (r:1 w:1)
+	// Storage: Did AttributeCount (r:1 w:1)
+	// Storage: Did ExpiryBuckets (r:1 w:1)
+	fn add_attribute_signed(v: u32) -> Weight {
+		(52_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Did AttributeNonce (r:1 w:1)
+	// Storage: Did AttributeStore (r:1 w:2)
+	// Storage: Did ExpiryBuckets (r:2 w:2)
+	fn update_attribute_signed(v: u32) -> Weight {
+		(55_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_100 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Did DelegateStore (r:0 w:1)
+	fn add_delegate() -> Weight {
+		(20_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Did DelegateStore (r:1 w:1)
+	fn revoke_delegate() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Did AttributeNonce (r:2 w:1)
+	// Storage: Did AttributeStore (r:2 w:2)
+	// Storage: Did AttributeCount (r:2 w:2)
+	// Storage: Did ExpiryBuckets (r:2 w:2)
+	fn transfer_attribute() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Did AttributeStore (r:a w:0)
+	fn read_all_attributes(a: u32) -> Weight {
+		(18_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((3_500 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads((a + 1) as Weight))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn add_attribute(v: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn update_attribute(v: u32) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((2_100 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn read_attribute() -> Weight {
+		(22_000_000 as Weight).saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+	fn remove_attribute() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn add_attribute_signed(v: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn update_attribute_signed(v: u32) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((2_100 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn add_delegate() -> Weight {
+		(20_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_delegate() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_attribute() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn read_all_attributes(a: u32) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((3_500 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads((a + 1) as Weight))
+	}
+}